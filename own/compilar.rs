@@ -1,6 +1,6 @@
 // Rust Online Compiler Server
 // Equivalent to compilar.py but implemented in Rust
-// 
+//
 // Dependencies needed in Cargo.toml:
 // [dependencies]
 // actix-web = "4.4"
@@ -10,21 +10,362 @@
 // tokio = { version = "1.0", features = ["full"] }
 // tempfile = "3.8"
 // uuid = { version = "1.6", features = ["v4"] }
+// libc = "0.2"
+// actix = "0.13"
+// actix-web-actors = "4.3"
 
+use actix::{Actor, AsyncContext, Handler, Message};
 use actix_files::Files;
-use actix_web::{web, App, HttpResponse, HttpServer, Result, middleware::Logger};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Result, middleware::Logger};
+use actix_web_actors::ws;
 use serde::{Deserialize, Serialize};
-use std::process::{Command, Stdio};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tempfile::NamedTempFile;
 use uuid::Uuid;
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+
+// Per-phase wall-clock and OS resource caps for one spawned process; compile
+// and run phases each get their own so a slow compiler doesn't eat into the
+// program's own budget.
+#[derive(Clone, Copy)]
+struct PhaseLimits {
+    wall_time: Duration,
+    // RLIMIT_CPU, in seconds.
+    cpu_seconds: u64,
+    // RLIMIT_AS, in bytes, or None to leave it uncapped. The JVM/V8 reserve
+    // a large address space up front regardless of actual usage, so a tight
+    // RLIMIT_AS kills them before they run any user code.
+    memory_bytes: Option<u64>,
+    // RLIMIT_FSIZE, in bytes, caps files (and redirected stdout) the child can write.
+    max_file_size_bytes: u64,
+    // RLIMIT_NPROC, to stop fork bombs. Accounted per (user namespace, uid),
+    // so it's only an isolated per-request budget under SANDBOX=namespace;
+    // under NoSandbox every request shares the host uid's budget.
+    max_processes: u64,
+}
+
+impl PhaseLimits {
+    fn compile() -> Self {
+        PhaseLimits {
+            wall_time: Duration::from_secs(20),
+            cpu_seconds: 20,
+            memory_bytes: Some(512 * 1024 * 1024),
+            max_file_size_bytes: 50 * 1024 * 1024,
+            max_processes: 64,
+        }
+    }
+
+    fn execution() -> Self {
+        PhaseLimits {
+            wall_time: Duration::from_secs(10),
+            cpu_seconds: 10,
+            memory_bytes: Some(256 * 1024 * 1024),
+            max_file_size_bytes: 10 * 1024 * 1024,
+            max_processes: 32,
+        }
+    }
+
+    // Drops the RLIMIT_AS cap for runtimes (JVM, V8) it can't meaningfully apply to.
+    fn without_memory_cap(mut self) -> Self {
+        self.memory_bytes = None;
+        self
+    }
+}
+
+// Resource budget for one /run request: compile and run steps capped separately.
+#[derive(Clone, Copy)]
+struct ExecutionLimits {
+    compile: PhaseLimits,
+    run: PhaseLimits,
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        ExecutionLimits {
+            compile: PhaseLimits::compile(),
+            run: PhaseLimits::execution(),
+        }
+    }
+}
+
+// Outcome of run_with_limits: finished, or killed for overrunning its wall-clock budget.
+enum RunOutcome {
+    Finished {
+        status: ExitStatus,
+        stdout: Vec<u8>,
+        stderr: Vec<u8>,
+    },
+    TimedOut,
+}
+
+// Applies setrlimit calls plus setsid (own process group, killable as a whole tree).
+#[cfg(unix)]
+fn confine(command: &mut Command, limits: PhaseLimits) {
+    unsafe {
+        command.pre_exec(move || {
+            set_rlimit(libc::RLIMIT_CPU, limits.cpu_seconds)?;
+            if let Some(memory_bytes) = limits.memory_bytes {
+                set_rlimit(libc::RLIMIT_AS, memory_bytes)?;
+            }
+            set_rlimit(libc::RLIMIT_FSIZE, limits.max_file_size_bytes)?;
+            set_rlimit(libc::RLIMIT_NPROC, limits.max_processes)?;
+            if libc::setsid() == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(unix)]
+fn set_rlimit(resource: libc::__rlimit_resource_t, value: u64) -> std::io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: value as libc::rlim_t,
+        rlim_max: value as libc::rlim_t,
+    };
+    if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn kill_process_group(child: &Child) {
+    unsafe {
+        // Negative pid targets the whole process group `setsid` placed the child in.
+        libc::kill(-(child.id() as i32), libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(child: &mut Child) {
+    let _ = child.kill();
+}
+
+// Spawns command with limits applied, polling for completion; SIGKILLs the
+// whole process group and returns TimedOut if wall_time elapses first.
+fn run_with_limits(
+    mut command: Command,
+    limits: PhaseLimits,
+    stdin: Option<&str>,
+) -> std::io::Result<RunOutcome> {
+    #[cfg(unix)]
+    confine(&mut command, limits);
+
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    if stdin.is_some() {
+        command.stdin(Stdio::piped());
+    }
+    let mut child = command.spawn()?;
+
+    // Write stdin on a background thread so a child that doesn't promptly
+    // drain it can't block this call before the deadline below is set.
+    if let Some(input) = stdin {
+        if let Some(mut child_stdin) = child.stdin.take() {
+            let input = input.to_string();
+            std::thread::spawn(move || {
+                use std::io::Write;
+                let _ = child_stdin.write_all(input.as_bytes());
+            });
+        }
+    }
+
+    // Drain stdout/stderr on background threads as they're produced, same as
+    // stream_with_limits, so a full pipe can't stall the poll loop below.
+    let stdout_reader = child.stdout.take().map(|mut out| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = out.read_to_end(&mut buf);
+            buf
+        })
+    });
+    let stderr_reader = child.stderr.take().map(|mut err| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = err.read_to_end(&mut buf);
+            buf
+        })
+    });
+
+    let deadline = Instant::now() + limits.wall_time;
+    let poll_interval = Duration::from_millis(25);
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            // Kill anything the child left behind in its group, so a leftover
+            // process holding the pipes open can't keep the joins below waiting.
+            kill_process_group(&mut child);
+            let stdout = stdout_reader.map(|h| h.join().unwrap_or_default()).unwrap_or_default();
+            let stderr = stderr_reader.map(|h| h.join().unwrap_or_default()).unwrap_or_default();
+            return Ok(RunOutcome::Finished { status, stdout, stderr });
+        }
+
+        if Instant::now() >= deadline {
+            kill_process_group(&mut child);
+            let _ = child.wait();
+            return Ok(RunOutcome::TimedOut);
+        }
+
+        std::thread::sleep(poll_interval);
+    }
+}
+
+// Runs command, returning Ok(Err(response)) pre-filled with a timeout
+// CodeResponse so callers can `return` it directly.
+fn output_within_limits(
+    command: Command,
+    limits: PhaseLimits,
+    stdin: Option<&str>,
+    start_time: Instant,
+) -> Result<std::result::Result<(ExitStatus, Vec<u8>, Vec<u8>), CodeResponse>, Box<dyn std::error::Error>> {
+    match run_with_limits(command, limits, stdin)? {
+        RunOutcome::Finished { status, stdout, stderr } => Ok(Ok((status, stdout, stderr))),
+        RunOutcome::TimedOut => Ok(Err(CodeResponse {
+            output: None,
+            error: Some("Time limit exceeded".to_string()),
+            execution_time: start_time.elapsed().as_secs_f64(),
+        })),
+    }
+}
+
+// A destination for a running program's output, delivered one line at a time.
+trait OutputSink: Send {
+    fn send_stdout(&mut self, line: String);
+    fn send_stderr(&mut self, line: String);
+}
+
+enum StreamLine {
+    Stdout(String),
+    Stderr(String),
+}
+
+// Like run_with_limits, but forwards each output line to sink as it arrives
+// instead of only returning the full buffers once the child exits.
+fn stream_with_limits(
+    mut command: Command,
+    limits: PhaseLimits,
+    stdin: Option<&str>,
+    sink: &mut dyn OutputSink,
+) -> std::io::Result<RunOutcome> {
+    #[cfg(unix)]
+    confine(&mut command, limits);
+
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    if stdin.is_some() {
+        command.stdin(Stdio::piped());
+    }
+    let mut child = command.spawn()?;
+
+    // See run_with_limits: write stdin on a background thread.
+    if let Some(input) = stdin {
+        if let Some(mut child_stdin) = child.stdin.take() {
+            let input = input.to_string();
+            std::thread::spawn(move || {
+                use std::io::Write;
+                let _ = child_stdin.write_all(input.as_bytes());
+            });
+        }
+    }
+
+    let (tx, rx) = mpsc::channel::<StreamLine>();
+
+    if let Some(out) = child.stdout.take() {
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(out).lines().map_while(Result::ok) {
+                if tx.send(StreamLine::Stdout(line)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    if let Some(err) = child.stderr.take() {
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(err).lines().map_while(Result::ok) {
+                if tx.send(StreamLine::Stderr(line)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(tx);
+
+    let deadline = Instant::now() + limits.wall_time;
+    let mut stdout_lines = Vec::new();
+    let mut stderr_lines = Vec::new();
+
+    loop {
+        // Checked every iteration, not only when the pipes go quiet, so a
+        // child that prints without pause still gets killed at the deadline.
+        if Instant::now() >= deadline {
+            kill_process_group(&mut child);
+            let _ = child.wait();
+            return Ok(RunOutcome::TimedOut);
+        }
+
+        match rx.recv_timeout(Duration::from_millis(50)) {
+            Ok(StreamLine::Stdout(line)) => {
+                sink.send_stdout(line.clone());
+                stdout_lines.push(line);
+            }
+            Ok(StreamLine::Stderr(line)) => {
+                sink.send_stderr(line.clone());
+                stderr_lines.push(line);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if let Some(status) = child.try_wait()? {
+                    return Ok(RunOutcome::Finished {
+                        status,
+                        stdout: stdout_lines.join("\n").into_bytes(),
+                        stderr: stderr_lines.join("\n").into_bytes(),
+                    });
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                let status = child.wait()?;
+                return Ok(RunOutcome::Finished {
+                    status,
+                    stdout: stdout_lines.join("\n").into_bytes(),
+                    stderr: stderr_lines.join("\n").into_bytes(),
+                });
+            }
+        }
+    }
+}
+
+// Streaming counterpart to output_within_limits.
+fn stream_output_within_limits(
+    command: Command,
+    limits: PhaseLimits,
+    stdin: Option<&str>,
+    sink: &mut dyn OutputSink,
+    start_time: Instant,
+) -> Result<std::result::Result<(ExitStatus, Vec<u8>, Vec<u8>), CodeResponse>, Box<dyn std::error::Error>> {
+    match stream_with_limits(command, limits, stdin, sink)? {
+        RunOutcome::Finished { status, stdout, stderr } => Ok(Ok((status, stdout, stderr))),
+        RunOutcome::TimedOut => Ok(Err(CodeResponse {
+            output: None,
+            error: Some("Time limit exceeded".to_string()),
+            execution_time: start_time.elapsed().as_secs_f64(),
+        })),
+    }
+}
 
 #[derive(Deserialize)]
 struct CodeRequest {
     code: String,
     language: String,
+    #[serde(default)]
+    stdin: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -135,7 +476,25 @@ const HTML_TEMPLATE: &str = r#"
         .output.error {
             color: #f48771;
         }
-        button { 
+        .stdin-header {
+            background: #2d2d30;
+            padding: 10px;
+            border-bottom: 1px solid #3e3e42;
+            border-top: 1px solid #3e3e42;
+            font-size: 14px;
+            font-weight: bold;
+        }
+        #stdin {
+            height: 80px;
+            background: #1e1e1e;
+            color: #d4d4d4;
+            border: none;
+            padding: 15px;
+            resize: vertical;
+            font-family: 'Consolas', 'Monaco', 'Courier New', monospace;
+            font-size: 14px;
+        }
+        button {
             padding: 10px 20px; 
             background: #0e639c; 
             color: white; 
@@ -188,6 +547,7 @@ const HTML_TEMPLATE: &str = r#"
             </select>
             <button onclick="runCode()" id="runBtn">▶ Run Code</button>
             <button onclick="clearOutput()">🗑 Clear Output</button>
+            <button onclick="shareSnippet()" id="shareBtn">🔗 Share</button>
         </div>
         
         <div class="editor-container">
@@ -201,24 +561,75 @@ const HTML_TEMPLATE: &str = r#"
             <div class="resizer" id="resizer"></div>
             
             <div class="output-panel">
+                <div class="stdin-header">Stdin (optional)</div>
+                <textarea id="stdin" placeholder="Input fed to the program's stdin, one value per line..."></textarea>
                 <div class="output-header">Output</div>
                 <div id="output" class="output">Output will appear here...</div>
             </div>
         </div>
     </div>
 
+    <!--SNIPPET_BOOTSTRAP-->
     <script>
         let editor;
-        
+
+        // LSP CompletionItemKind numbers, indexed so LSP_KIND_NAMES[kind]
+        // gives the matching monaco.languages.CompletionItemKind name.
+        const LSP_KIND_NAMES = ['', 'Text', 'Method', 'Function', 'Constructor', 'Field', 'Variable',
+            'Class', 'Interface', 'Module', 'Property', 'Unit', 'Value', 'Enum', 'Keyword', 'Snippet',
+            'Color', 'File', 'Reference', 'Folder', 'EnumMember', 'Constant', 'Struct', 'Event',
+            'Operator', 'TypeParameter'];
+
+        // Registers a `/complete`-backed completion provider for one Monaco
+        // language id; languages the server has no language server for just
+        // get back an empty suggestion list.
+        function registerCompletionProvider(language) {
+            monaco.languages.registerCompletionItemProvider(language, {
+                triggerCharacters: ['.', ':'],
+                provideCompletionItems: function(model, position) {
+                    const word = model.getWordUntilPosition(position);
+                    const range = {
+                        startLineNumber: position.lineNumber,
+                        endLineNumber: position.lineNumber,
+                        startColumn: word.startColumn,
+                        endColumn: word.endColumn
+                    };
+                    return fetch('/complete', {
+                        method: 'POST',
+                        headers: { 'Content-Type': 'application/json' },
+                        body: JSON.stringify({
+                            code: model.getValue(),
+                            language: language,
+                            line: position.lineNumber - 1,
+                            character: position.column - 1
+                        })
+                    })
+                        .then(response => response.json())
+                        .then(data => ({
+                            suggestions: data.items.map(function(item) {
+                                return {
+                                    label: item.label,
+                                    kind: monaco.languages.CompletionItemKind[LSP_KIND_NAMES[item.kind] || 'Text'],
+                                    insertText: item.insertText || item.label,
+                                    range: range
+                                };
+                            })
+                        }))
+                        .catch(() => ({ suggestions: [] }));
+                }
+            });
+        }
+
         // Initialize Monaco Editor
         require.config({ paths: { vs: '/monaco-editor/vs' }});
         require(['vs/editor/editor.main'], function () {
+            const snippet = window.__SNIPPET;
             editor = monaco.editor.create(document.getElementById('editor'), {
-                value: `# Python example
+                value: snippet ? snippet.code : `# Python example
 print("Hello, World!")
 for i in range(3):
     print(f"Count: {i}")`,
-                language: 'python',
+                language: snippet ? snippet.language : 'python',
                 theme: 'vs-dark',
                 fontSize: 14,
                 minimap: { enabled: false },
@@ -228,8 +639,18 @@ for i in range(3):
                 tabSize: 4,
                 insertSpaces: true
             });
+
+            if (snippet) {
+                document.getElementById('language').value = snippet.language;
+                const example = examples[snippet.language];
+                if (example) {
+                    document.getElementById('filename').textContent = example.filename;
+                }
+            }
+
+            ['python', 'rust', 'c', 'cpp'].forEach(registerCompletionProvider);
         });
-        
+
         // Language examples
         const examples = {
             python: {
@@ -366,48 +787,55 @@ WHERE salary > (SELECT AVG(salary) FROM employees);`,
             }
         });
         
-        async function runCode() {
+        function runCode() {
             if (!editor) {
                 alert('Editor not ready yet. Please wait a moment and try again.');
                 return;
             }
-            
+
             const code = editor.getValue();
             const language = document.getElementById('language').value;
+            const stdin = document.getElementById('stdin').value;
             const output = document.getElementById('output');
             const runBtn = document.getElementById('runBtn');
-            
+
             // Update UI for running state
-            output.textContent = 'Running...';
+            output.textContent = '';
             output.className = 'output loading';
             runBtn.disabled = true;
             runBtn.textContent = '⏳ Running...';
-            
-            try {
-                const response = await fetch('/run', {
-                    method: 'POST',
-                    headers: { 'Content-Type': 'application/json' },
-                    body: JSON.stringify({ code: code, language: language })
-                });
-                
-                const result = await response.json();
-                
-                if (result.error) {
-                    const executionTime = result.execution_time ? `\n\n⏱ Execution Time: ${result.execution_time.toFixed(3)} seconds` : '';
-                    output.textContent = result.error + executionTime;
-                    output.className = 'output error';
-                } else {
-                    const executionTime = result.execution_time ? `\n\n⏱ Execution Time: ${result.execution_time.toFixed(3)} seconds` : '';
-                    output.textContent = (result.output || 'Program executed successfully (no output)') + executionTime;
-                    output.className = 'output';
+
+            let sawOutput = false;
+            const wsProtocol = location.protocol === 'https:' ? 'wss:' : 'ws:';
+            const socket = new WebSocket(`${wsProtocol}//${location.host}/run/ws`);
+
+            socket.onopen = () => {
+                socket.send(JSON.stringify({ code: code, language: language, stdin: stdin }));
+            };
+
+            socket.onmessage = (event) => {
+                const frame = JSON.parse(event.data);
+                if (frame.event === 'output') {
+                    sawOutput = true;
+                    output.className = frame.stream === 'stderr' ? 'output error' : 'output';
+                    output.textContent += frame.chunk + '\n';
+                } else if (frame.event === 'exit') {
+                    if (!sawOutput) {
+                        output.textContent = 'Program executed successfully (no output)';
+                    }
+                    output.textContent += `\n\n⏱ Execution Time: ${frame.execution_time.toFixed(3)} seconds`;
+                    runBtn.disabled = false;
+                    runBtn.textContent = '▶ Run Code';
+                    socket.close();
                 }
-            } catch (error) {
-                output.textContent = 'Network Error: ' + error.message;
+            };
+
+            socket.onerror = () => {
+                output.textContent = 'WebSocket Error: could not reach /run/ws';
                 output.className = 'output error';
-            } finally {
                 runBtn.disabled = false;
                 runBtn.textContent = '▶ Run Code';
-            }
+            };
         }
         
         function clearOutput() {
@@ -415,7 +843,41 @@ WHERE salary > (SELECT AVG(salary) FROM employees);`,
             output.textContent = 'Output will appear here...';
             output.className = 'output';
         }
-        
+
+        function shareSnippet() {
+            if (!editor) {
+                alert('Editor not ready yet. Please wait a moment and try again.');
+                return;
+            }
+
+            const shareBtn = document.getElementById('shareBtn');
+            shareBtn.disabled = true;
+
+            fetch('/share', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({
+                    code: editor.getValue(),
+                    language: document.getElementById('language').value
+                })
+            })
+                .then(response => response.json())
+                .then(data => {
+                    const url = new URL(data.url, location.href).toString();
+                    if (navigator.clipboard) {
+                        navigator.clipboard.writeText(url);
+                    }
+                    shareBtn.textContent = '✅ Copied!';
+                    setTimeout(() => { shareBtn.textContent = '🔗 Share'; }, 2000);
+                })
+                .catch(() => {
+                    alert('Could not create a share link.');
+                })
+                .finally(() => {
+                    shareBtn.disabled = false;
+                });
+        }
+
         // Add keyboard shortcut for running code (Ctrl+Enter or Cmd+Enter)
         document.addEventListener('keydown', function(e) {
             if ((e.ctrlKey || e.metaKey) && e.key === 'Enter') {
@@ -469,42 +931,190 @@ WHERE salary > (SELECT AVG(salary) FROM employees);`,
 </html>
 "#;
 
+// Builds the Command a CodeRunner should spawn for program, wrapped in
+// whatever host isolation the configured backend provides. workdir is the
+// directory holding the snippet's files, which the sandbox grants access to;
+// for single-temp-file runners that is the shared temp directory itself.
+trait Sandbox: Send + Sync {
+    fn prepare(&self, program: &str, workdir: &Path) -> Command;
+}
+
+// Runs program directly on the host, exactly as this server always has.
+struct NoSandbox;
+
+impl Sandbox for NoSandbox {
+    fn prepare(&self, program: &str, _workdir: &Path) -> Command {
+        Command::new(program)
+    }
+}
+
+// Runs program inside fresh mount/PID/network/user namespaces via unshare,
+// chrooted into a read-only rootfs with workdir bind-mounted read-write at
+// its own path (so existing absolute paths keep working once chrooted).
+struct NamespaceSandbox {
+    rootfs_dir: PathBuf,
+}
+
+impl Default for NamespaceSandbox {
+    fn default() -> Self {
+        let rootfs_dir = std::env::var("SANDBOX_ROOTFS")
+            .unwrap_or_else(|_| "/opt/sandbox-rootfs".to_string())
+            .into();
+        NamespaceSandbox { rootfs_dir }
+    }
+}
+
+impl Sandbox for NamespaceSandbox {
+    fn prepare(&self, program: &str, workdir: &Path) -> Command {
+        let rootfs = self.rootfs_dir.display();
+        let mirrored = workdir.display();
+        let script = format!(
+            "set -e
+             mount --bind {rootfs} {rootfs}
+             mkdir -p {rootfs}{mirrored}
+             mount --bind {mirrored} {rootfs}{mirrored}
+             mount -o remount,ro,bind {rootfs}
+             exec chroot {rootfs} /bin/sh -c 'cd {mirrored} && exec \"$@\"' sh \"$@\""
+        );
+
+        let mut command = Command::new("unshare");
+        command
+            .arg("--mount")
+            .arg("--pid")
+            .arg("--net")
+            .arg("--user")
+            .arg("--map-root-user")
+            .arg("--fork")
+            .arg("--")
+            .arg("/bin/sh")
+            .arg("-c")
+            .arg(script)
+            .arg("sh")
+            .arg(program);
+        command
+    }
+}
+
+// Selects the configured sandbox backend once, from the SANDBOX env var.
+fn sandbox() -> &'static dyn Sandbox {
+    static SANDBOX: OnceLock<Box<dyn Sandbox>> = OnceLock::new();
+    SANDBOX
+        .get_or_init(|| -> Box<dyn Sandbox> {
+            match std::env::var("SANDBOX").as_deref() {
+                Ok("namespace") => Box::new(NamespaceSandbox::default()),
+                _ => Box::new(NoSandbox),
+            }
+        })
+        .as_ref()
+}
+
+// The directory to scope sandbox access to for a runner whose snippet is a single temp file.
+fn parent_dir(path: &Path) -> &Path {
+    path.parent().unwrap_or_else(|| Path::new("/tmp"))
+}
+
 // Language runners
+trait CodeRunner {
+    fn run(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>>;
+
+    // Same as run, but pushes output lines to sink as produced; default
+    // just replays run's buffered output in one shot.
+    fn run_streaming(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+        sink: &mut dyn OutputSink,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+        let response = self.run(code, stdin, limits)?;
+        if let Some(output) = &response.output {
+            sink.send_stdout(output.clone());
+        }
+        if let Some(error) = &response.error {
+            sink.send_stderr(error.clone());
+        }
+        Ok(response)
+    }
+}
+
 impl CodeRunner for PythonRunner {
-    fn run(&self, code: &str) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+    fn run(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
         let start_time = Instant::now();
-        
+
         // Create temporary file
         let mut temp_file = NamedTempFile::new()?;
         fs::write(temp_file.path(), code)?;
-        
+
         // Execute Python code
-        let output = Command::new("python3")
-            .arg(temp_file.path())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()?;
-        
+        let mut command = sandbox().prepare("python3", parent_dir(temp_file.path()));
+        command.arg(temp_file.path());
+        let (_, stdout, stderr) = match output_within_limits(command, limits.run, stdin, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
         let execution_time = start_time.elapsed().as_secs_f64();
-        
-        if !output.stderr.is_empty() {
+
+        if !stderr.is_empty() {
             Ok(CodeResponse {
                 output: None,
-                error: Some(String::from_utf8_lossy(&output.stderr).to_string()),
+                error: Some(String::from_utf8_lossy(&stderr).to_string()),
                 execution_time,
             })
         } else {
             Ok(CodeResponse {
-                output: Some(String::from_utf8_lossy(&output.stdout).to_string()),
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
                 error: None,
                 execution_time,
             })
         }
     }
-}
 
-trait CodeRunner {
-    fn run(&self, code: &str) -> Result<CodeResponse, Box<dyn std::error::Error>>;
+    fn run_streaming(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+        sink: &mut dyn OutputSink,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+        let start_time = Instant::now();
+
+        let mut temp_file = NamedTempFile::new()?;
+        fs::write(temp_file.path(), code)?;
+
+        let mut command = sandbox().prepare("python3", parent_dir(temp_file.path()));
+        command.arg(temp_file.path());
+        let (_, stdout, stderr) = match stream_output_within_limits(command, limits.run, stdin, sink, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        let execution_time = start_time.elapsed().as_secs_f64();
+
+        if !stderr.is_empty() {
+            Ok(CodeResponse {
+                output: None,
+                error: Some(String::from_utf8_lossy(&stderr).to_string()),
+                execution_time,
+            })
+        } else {
+            Ok(CodeResponse {
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
+                error: None,
+                execution_time,
+            })
+        }
+    }
 }
 
 struct PythonRunner;
@@ -518,100 +1128,113 @@ struct SqlRunner;
 struct TextRunner;
 
 impl CodeRunner for CRunner {
-    fn run(&self, code: &str) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+    fn run(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
         let start_time = Instant::now();
-        
+
         // Create temporary file
         let temp_file = NamedTempFile::with_suffix(".c")?;
         fs::write(temp_file.path(), code)?;
-        
+
         let exe_path = temp_file.path().with_extension("");
-        
+
         // Compile
-        let compile_output = Command::new("gcc")
-            .args(&[temp_file.path().to_str().unwrap(), "-o", exe_path.to_str().unwrap()])
-            .output()?;
-        
-        if !compile_output.status.success() {
+        let mut compile_command = sandbox().prepare("gcc", parent_dir(temp_file.path()));
+        compile_command.args(&[temp_file.path().to_str().unwrap(), "-o", exe_path.to_str().unwrap()]);
+        let (compile_status, _, compile_stderr) = match output_within_limits(compile_command, limits.compile, None, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        if !compile_status.success() {
             let execution_time = start_time.elapsed().as_secs_f64();
             return Ok(CodeResponse {
                 output: None,
-                error: Some(format!("Compilation Error:\n{}", String::from_utf8_lossy(&compile_output.stderr))),
+                error: Some(format!("Compilation Error:\n{}", String::from_utf8_lossy(&compile_stderr))),
                 execution_time,
             });
         }
-        
+
         // Execute
-        let run_output = Command::new(&exe_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()?;
-        
+        let run_command = sandbox().prepare(exe_path.to_str().unwrap(), parent_dir(&exe_path));
+        let (_, stdout, stderr) = match output_within_limits(run_command, limits.run, stdin, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
         let execution_time = start_time.elapsed().as_secs_f64();
-        
+
         // Cleanup
         let _ = fs::remove_file(&exe_path);
-        
-        if !run_output.stderr.is_empty() {
+
+        if !stderr.is_empty() {
             Ok(CodeResponse {
                 output: None,
-                error: Some(String::from_utf8_lossy(&run_output.stderr).to_string()),
+                error: Some(String::from_utf8_lossy(&stderr).to_string()),
                 execution_time,
             })
         } else {
             Ok(CodeResponse {
-                output: Some(String::from_utf8_lossy(&run_output.stdout).to_string()),
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
                 error: None,
                 execution_time,
             })
         }
     }
-}
 
-impl CodeRunner for CppRunner {
-    fn run(&self, code: &str) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+    fn run_streaming(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+        sink: &mut dyn OutputSink,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
         let start_time = Instant::now();
-        
-        // Create temporary file
-        let temp_file = NamedTempFile::with_suffix(".cpp")?;
+
+        let temp_file = NamedTempFile::with_suffix(".c")?;
         fs::write(temp_file.path(), code)?;
-        
+
         let exe_path = temp_file.path().with_extension("");
-        
-        // Compile
-        let compile_output = Command::new("g++")
-            .args(&[temp_file.path().to_str().unwrap(), "-o", exe_path.to_str().unwrap()])
-            .output()?;
-        
-        if !compile_output.status.success() {
+
+        let mut compile_command = sandbox().prepare("gcc", parent_dir(temp_file.path()));
+        compile_command.args(&[temp_file.path().to_str().unwrap(), "-o", exe_path.to_str().unwrap()]);
+        let (compile_status, _, compile_stderr) = match output_within_limits(compile_command, limits.compile, None, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        if !compile_status.success() {
             let execution_time = start_time.elapsed().as_secs_f64();
             return Ok(CodeResponse {
                 output: None,
-                error: Some(format!("Compilation Error:\n{}", String::from_utf8_lossy(&compile_output.stderr))),
+                error: Some(format!("Compilation Error:\n{}", String::from_utf8_lossy(&compile_stderr))),
                 execution_time,
             });
         }
-        
-        // Execute
-        let run_output = Command::new(&exe_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()?;
-        
+
+        let run_command = sandbox().prepare(exe_path.to_str().unwrap(), parent_dir(&exe_path));
+        let (_, stdout, stderr) = match stream_output_within_limits(run_command, limits.run, stdin, sink, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
         let execution_time = start_time.elapsed().as_secs_f64();
-        
-        // Cleanup
+
         let _ = fs::remove_file(&exe_path);
-        
-        if !run_output.stderr.is_empty() {
+
+        if !stderr.is_empty() {
             Ok(CodeResponse {
                 output: None,
-                error: Some(String::from_utf8_lossy(&run_output.stderr).to_string()),
+                error: Some(String::from_utf8_lossy(&stderr).to_string()),
                 execution_time,
             })
         } else {
             Ok(CodeResponse {
-                output: Some(String::from_utf8_lossy(&run_output.stdout).to_string()),
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
                 error: None,
                 execution_time,
             })
@@ -619,10 +1242,130 @@ impl CodeRunner for CppRunner {
     }
 }
 
-impl CodeRunner for JavaRunner {
-    fn run(&self, code: &str) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+impl CodeRunner for CppRunner {
+    fn run(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
         let start_time = Instant::now();
-        
+
+        // Create temporary file
+        let temp_file = NamedTempFile::with_suffix(".cpp")?;
+        fs::write(temp_file.path(), code)?;
+
+        let exe_path = temp_file.path().with_extension("");
+
+        // Compile
+        let mut compile_command = sandbox().prepare("g++", parent_dir(temp_file.path()));
+        compile_command.args(&[temp_file.path().to_str().unwrap(), "-o", exe_path.to_str().unwrap()]);
+        let (compile_status, _, compile_stderr) = match output_within_limits(compile_command, limits.compile, None, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        if !compile_status.success() {
+            let execution_time = start_time.elapsed().as_secs_f64();
+            return Ok(CodeResponse {
+                output: None,
+                error: Some(format!("Compilation Error:\n{}", String::from_utf8_lossy(&compile_stderr))),
+                execution_time,
+            });
+        }
+
+        // Execute
+        let run_command = sandbox().prepare(exe_path.to_str().unwrap(), parent_dir(&exe_path));
+        let (_, stdout, stderr) = match output_within_limits(run_command, limits.run, stdin, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        let execution_time = start_time.elapsed().as_secs_f64();
+
+        // Cleanup
+        let _ = fs::remove_file(&exe_path);
+
+        if !stderr.is_empty() {
+            Ok(CodeResponse {
+                output: None,
+                error: Some(String::from_utf8_lossy(&stderr).to_string()),
+                execution_time,
+            })
+        } else {
+            Ok(CodeResponse {
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
+                error: None,
+                execution_time,
+            })
+        }
+    }
+
+    fn run_streaming(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+        sink: &mut dyn OutputSink,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+        let start_time = Instant::now();
+
+        let temp_file = NamedTempFile::with_suffix(".cpp")?;
+        fs::write(temp_file.path(), code)?;
+
+        let exe_path = temp_file.path().with_extension("");
+
+        let mut compile_command = sandbox().prepare("g++", parent_dir(temp_file.path()));
+        compile_command.args(&[temp_file.path().to_str().unwrap(), "-o", exe_path.to_str().unwrap()]);
+        let (compile_status, _, compile_stderr) = match output_within_limits(compile_command, limits.compile, None, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        if !compile_status.success() {
+            let execution_time = start_time.elapsed().as_secs_f64();
+            return Ok(CodeResponse {
+                output: None,
+                error: Some(format!("Compilation Error:\n{}", String::from_utf8_lossy(&compile_stderr))),
+                execution_time,
+            });
+        }
+
+        let run_command = sandbox().prepare(exe_path.to_str().unwrap(), parent_dir(&exe_path));
+        let (_, stdout, stderr) = match stream_output_within_limits(run_command, limits.run, stdin, sink, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        let execution_time = start_time.elapsed().as_secs_f64();
+
+        let _ = fs::remove_file(&exe_path);
+
+        if !stderr.is_empty() {
+            Ok(CodeResponse {
+                output: None,
+                error: Some(String::from_utf8_lossy(&stderr).to_string()),
+                execution_time,
+            })
+        } else {
+            Ok(CodeResponse {
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
+                error: None,
+                execution_time,
+            })
+        }
+    }
+}
+
+impl CodeRunner for JavaRunner {
+    fn run(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+        let start_time = Instant::now();
+
         // Extract class name from code
         let mut class_name = "Main".to_string();
         for line in code.lines() {
@@ -633,44 +1376,111 @@ impl CodeRunner for JavaRunner {
                 }
             }
         }
-        
+
         // Create temporary directory and file
         let temp_dir = tempfile::tempdir()?;
         let java_file = temp_dir.path().join(format!("{}.java", class_name));
         fs::write(&java_file, code)?;
-        
+
         // Compile
-        let compile_output = Command::new("javac")
-            .arg(&java_file)
-            .output()?;
-        
-        if !compile_output.status.success() {
+        let mut compile_command = sandbox().prepare("javac", temp_dir.path());
+        compile_command.arg(&java_file);
+        let (compile_status, _, compile_stderr) = match output_within_limits(compile_command, limits.compile.without_memory_cap(), None, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        if !compile_status.success() {
             let execution_time = start_time.elapsed().as_secs_f64();
             return Ok(CodeResponse {
                 output: None,
-                error: Some(format!("Compilation Error:\n{}", String::from_utf8_lossy(&compile_output.stderr))),
+                error: Some(format!("Compilation Error:\n{}", String::from_utf8_lossy(&compile_stderr))),
                 execution_time,
             });
         }
-        
+
         // Execute
-        let run_output = Command::new("java")
-            .args(&["-cp", temp_dir.path().to_str().unwrap(), &class_name])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()?;
-        
+        let mut run_command = sandbox().prepare("java", temp_dir.path());
+        run_command.args(&["-cp", temp_dir.path().to_str().unwrap(), &class_name]);
+        let (_, stdout, stderr) = match output_within_limits(run_command, limits.run.without_memory_cap(), stdin, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
         let execution_time = start_time.elapsed().as_secs_f64();
-        
-        if !run_output.stderr.is_empty() {
+
+        if !stderr.is_empty() {
+            Ok(CodeResponse {
+                output: None,
+                error: Some(String::from_utf8_lossy(&stderr).to_string()),
+                execution_time,
+            })
+        } else {
+            Ok(CodeResponse {
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
+                error: None,
+                execution_time,
+            })
+        }
+    }
+
+    fn run_streaming(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+        sink: &mut dyn OutputSink,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+        let start_time = Instant::now();
+
+        let mut class_name = "Main".to_string();
+        for line in code.lines() {
+            if line.contains("public class") {
+                if let Some(name) = line.split("public class").nth(1) {
+                    class_name = name.split('{').next().unwrap_or("Main").trim().to_string();
+                    break;
+                }
+            }
+        }
+
+        let temp_dir = tempfile::tempdir()?;
+        let java_file = temp_dir.path().join(format!("{}.java", class_name));
+        fs::write(&java_file, code)?;
+
+        let mut compile_command = sandbox().prepare("javac", temp_dir.path());
+        compile_command.arg(&java_file);
+        let (compile_status, _, compile_stderr) = match output_within_limits(compile_command, limits.compile.without_memory_cap(), None, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        if !compile_status.success() {
+            let execution_time = start_time.elapsed().as_secs_f64();
+            return Ok(CodeResponse {
+                output: None,
+                error: Some(format!("Compilation Error:\n{}", String::from_utf8_lossy(&compile_stderr))),
+                execution_time,
+            });
+        }
+
+        let mut run_command = sandbox().prepare("java", temp_dir.path());
+        run_command.args(&["-cp", temp_dir.path().to_str().unwrap(), &class_name]);
+        let (_, stdout, stderr) = match stream_output_within_limits(run_command, limits.run.without_memory_cap(), stdin, sink, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        let execution_time = start_time.elapsed().as_secs_f64();
+
+        if !stderr.is_empty() {
             Ok(CodeResponse {
                 output: None,
-                error: Some(String::from_utf8_lossy(&run_output.stderr).to_string()),
+                error: Some(String::from_utf8_lossy(&stderr).to_string()),
                 execution_time,
             })
         } else {
             Ok(CodeResponse {
-                output: Some(String::from_utf8_lossy(&run_output.stdout).to_string()),
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
                 error: None,
                 execution_time,
             })
@@ -679,55 +1489,125 @@ impl CodeRunner for JavaRunner {
 }
 
 impl CodeRunner for KotlinRunner {
-    fn run(&self, code: &str) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+    fn run(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
         let start_time = Instant::now();
-        
+
         // Create temporary file
         let temp_file = NamedTempFile::with_suffix(".kt")?;
         fs::write(temp_file.path(), code)?;
-        
+
         let jar_path = temp_file.path().with_extension("jar");
-        
+
         // Compile
-        let compile_output = Command::new("kotlinc")
-            .args(&[
-                temp_file.path().to_str().unwrap(),
-                "-include-runtime",
-                "-d",
-                jar_path.to_str().unwrap()
-            ])
-            .output()?;
-        
-        if !compile_output.status.success() {
+        let mut compile_command = sandbox().prepare("kotlinc", parent_dir(temp_file.path()));
+        compile_command.args(&[
+            temp_file.path().to_str().unwrap(),
+            "-include-runtime",
+            "-d",
+            jar_path.to_str().unwrap(),
+        ]);
+        let (compile_status, _, compile_stderr) = match output_within_limits(compile_command, limits.compile.without_memory_cap(), None, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        if !compile_status.success() {
             let execution_time = start_time.elapsed().as_secs_f64();
             return Ok(CodeResponse {
                 output: None,
-                error: Some(format!("Compilation Error:\n{}", String::from_utf8_lossy(&compile_output.stderr))),
+                error: Some(format!("Compilation Error:\n{}", String::from_utf8_lossy(&compile_stderr))),
                 execution_time,
             });
         }
-        
+
         // Execute
-        let run_output = Command::new("java")
-            .args(&["-jar", jar_path.to_str().unwrap()])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()?;
-        
+        let mut run_command = sandbox().prepare("java", parent_dir(&jar_path));
+        run_command.args(&["-jar", jar_path.to_str().unwrap()]);
+        let (_, stdout, stderr) = match output_within_limits(run_command, limits.run.without_memory_cap(), stdin, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
         let execution_time = start_time.elapsed().as_secs_f64();
-        
+
         // Cleanup
         let _ = fs::remove_file(&jar_path);
-        
-        if !run_output.stderr.is_empty() {
+
+        if !stderr.is_empty() {
+            Ok(CodeResponse {
+                output: None,
+                error: Some(String::from_utf8_lossy(&stderr).to_string()),
+                execution_time,
+            })
+        } else {
+            Ok(CodeResponse {
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
+                error: None,
+                execution_time,
+            })
+        }
+    }
+
+    fn run_streaming(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+        sink: &mut dyn OutputSink,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+        let start_time = Instant::now();
+
+        let temp_file = NamedTempFile::with_suffix(".kt")?;
+        fs::write(temp_file.path(), code)?;
+
+        let jar_path = temp_file.path().with_extension("jar");
+
+        let mut compile_command = sandbox().prepare("kotlinc", parent_dir(temp_file.path()));
+        compile_command.args(&[
+            temp_file.path().to_str().unwrap(),
+            "-include-runtime",
+            "-d",
+            jar_path.to_str().unwrap(),
+        ]);
+        let (compile_status, _, compile_stderr) = match output_within_limits(compile_command, limits.compile.without_memory_cap(), None, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        if !compile_status.success() {
+            let execution_time = start_time.elapsed().as_secs_f64();
+            return Ok(CodeResponse {
+                output: None,
+                error: Some(format!("Compilation Error:\n{}", String::from_utf8_lossy(&compile_stderr))),
+                execution_time,
+            });
+        }
+
+        let mut run_command = sandbox().prepare("java", parent_dir(&jar_path));
+        run_command.args(&["-jar", jar_path.to_str().unwrap()]);
+        let (_, stdout, stderr) = match stream_output_within_limits(run_command, limits.run.without_memory_cap(), stdin, sink, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        let execution_time = start_time.elapsed().as_secs_f64();
+
+        let _ = fs::remove_file(&jar_path);
+
+        if !stderr.is_empty() {
             Ok(CodeResponse {
                 output: None,
-                error: Some(String::from_utf8_lossy(&run_output.stderr).to_string()),
+                error: Some(String::from_utf8_lossy(&stderr).to_string()),
                 execution_time,
             })
         } else {
             Ok(CodeResponse {
-                output: Some(String::from_utf8_lossy(&run_output.stdout).to_string()),
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
                 error: None,
                 execution_time,
             })
@@ -736,31 +1616,73 @@ impl CodeRunner for KotlinRunner {
 }
 
 impl CodeRunner for JavaScriptRunner {
-    fn run(&self, code: &str) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+    fn run(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
         let start_time = Instant::now();
-        
+
         // Create temporary file
         let temp_file = NamedTempFile::with_suffix(".js")?;
         fs::write(temp_file.path(), code)?;
-        
+
         // Execute
-        let output = Command::new("node")
-            .arg(temp_file.path())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()?;
-        
+        let mut command = sandbox().prepare("node", parent_dir(temp_file.path()));
+        command.arg(temp_file.path());
+        let (_, stdout, stderr) = match output_within_limits(command, limits.run.without_memory_cap(), stdin, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
         let execution_time = start_time.elapsed().as_secs_f64();
-        
-        if !output.stderr.is_empty() {
+
+        if !stderr.is_empty() {
             Ok(CodeResponse {
                 output: None,
-                error: Some(String::from_utf8_lossy(&output.stderr).to_string()),
+                error: Some(String::from_utf8_lossy(&stderr).to_string()),
                 execution_time,
             })
         } else {
             Ok(CodeResponse {
-                output: Some(String::from_utf8_lossy(&output.stdout).to_string()),
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
+                error: None,
+                execution_time,
+            })
+        }
+    }
+
+    fn run_streaming(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+        sink: &mut dyn OutputSink,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+        let start_time = Instant::now();
+
+        let temp_file = NamedTempFile::with_suffix(".js")?;
+        fs::write(temp_file.path(), code)?;
+
+        let mut command = sandbox().prepare("node", parent_dir(temp_file.path()));
+        command.arg(temp_file.path());
+        let (_, stdout, stderr) = match stream_output_within_limits(command, limits.run.without_memory_cap(), stdin, sink, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        let execution_time = start_time.elapsed().as_secs_f64();
+
+        if !stderr.is_empty() {
+            Ok(CodeResponse {
+                output: None,
+                error: Some(String::from_utf8_lossy(&stderr).to_string()),
+                execution_time,
+            })
+        } else {
+            Ok(CodeResponse {
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
                 error: None,
                 execution_time,
             })
@@ -769,14 +1691,19 @@ impl CodeRunner for JavaScriptRunner {
 }
 
 impl CodeRunner for RustRunner {
-    fn run(&self, code: &str) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+    fn run(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
         let start_time = Instant::now();
-        
+
         // Create temporary Cargo project
         let temp_dir = tempfile::tempdir()?;
         let project_dir = temp_dir.path().join("rust_project");
         fs::create_dir_all(&project_dir)?;
-        
+
         // Create Cargo.toml
         let cargo_toml = r#"[package]
 name = "rust_project"
@@ -788,31 +1715,84 @@ name = "main"
 path = "src/main.rs"
 "#;
         fs::write(project_dir.join("Cargo.toml"), cargo_toml)?;
-        
+
         // Create src directory and main.rs
         let src_dir = project_dir.join("src");
         fs::create_dir_all(&src_dir)?;
         fs::write(src_dir.join("main.rs"), code)?;
-        
-        // Compile and run
-        let output = Command::new("cargo")
-            .args(&["run", "--quiet"])
-            .current_dir(&project_dir)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()?;
-        
+
+        // Compile and run: `cargo run` itself does both, so it gets the
+        // more generous compile budget rather than the tighter run budget.
+        let mut command = sandbox().prepare("cargo", &project_dir);
+        command.args(&["run", "--quiet"]).current_dir(&project_dir);
+        let (status, stdout, stderr) = match output_within_limits(command, limits.compile, stdin, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
         let execution_time = start_time.elapsed().as_secs_f64();
-        
-        if !output.status.success() {
+
+        if !status.success() {
+            Ok(CodeResponse {
+                output: None,
+                error: Some(format!("Compilation/Runtime Error:\n{}", String::from_utf8_lossy(&stderr))),
+                execution_time,
+            })
+        } else {
+            Ok(CodeResponse {
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
+                error: None,
+                execution_time,
+            })
+        }
+    }
+
+    fn run_streaming(
+        &self,
+        code: &str,
+        stdin: Option<&str>,
+        limits: &ExecutionLimits,
+        sink: &mut dyn OutputSink,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+        let start_time = Instant::now();
+
+        let temp_dir = tempfile::tempdir()?;
+        let project_dir = temp_dir.path().join("rust_project");
+        fs::create_dir_all(&project_dir)?;
+
+        let cargo_toml = r#"[package]
+name = "rust_project"
+version = "0.1.0"
+edition = "2021"
+
+[[bin]]
+name = "main"
+path = "src/main.rs"
+"#;
+        fs::write(project_dir.join("Cargo.toml"), cargo_toml)?;
+
+        let src_dir = project_dir.join("src");
+        fs::create_dir_all(&src_dir)?;
+        fs::write(src_dir.join("main.rs"), code)?;
+
+        let mut command = sandbox().prepare("cargo", &project_dir);
+        command.args(&["run", "--quiet"]).current_dir(&project_dir);
+        let (status, stdout, stderr) = match stream_output_within_limits(command, limits.compile, stdin, sink, start_time)? {
+            Ok(output) => output,
+            Err(timeout_response) => return Ok(timeout_response),
+        };
+
+        let execution_time = start_time.elapsed().as_secs_f64();
+
+        if !status.success() {
             Ok(CodeResponse {
                 output: None,
-                error: Some(format!("Compilation/Runtime Error:\n{}", String::from_utf8_lossy(&output.stderr))),
+                error: Some(format!("Compilation/Runtime Error:\n{}", String::from_utf8_lossy(&stderr))),
                 execution_time,
             })
         } else {
             Ok(CodeResponse {
-                output: Some(String::from_utf8_lossy(&output.stdout).to_string()),
+                output: Some(String::from_utf8_lossy(&stdout).to_string()),
                 error: None,
                 execution_time,
             })
@@ -821,16 +1801,21 @@ path = "src/main.rs"
 }
 
 impl CodeRunner for SqlRunner {
-    fn run(&self, code: &str) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+    fn run(
+        &self,
+        code: &str,
+        _stdin: Option<&str>,
+        limits: &ExecutionLimits,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
         let start_time = Instant::now();
-        
+
         // Create temporary SQLite database
         let temp_db = NamedTempFile::with_suffix(".db")?;
-        
+
         // Split code into statements
         let mut statements = Vec::new();
         let mut current_statement = String::new();
-        
+
         for line in code.lines() {
             let line = line.trim();
             if line.starts_with("--") || line.is_empty() {
@@ -843,32 +1828,30 @@ impl CodeRunner for SqlRunner {
                 current_statement.clear();
             }
         }
-        
+
         if !current_statement.trim().is_empty() {
             statements.push(current_statement.trim().to_string());
         }
-        
+
         let mut results = Vec::new();
-        
+
         for statement in statements {
             if statement.trim().is_empty() {
                 continue;
             }
-            
-            let output = Command::new("sqlite3")
-                .args(&[
-                    temp_db.path().to_str().unwrap(),
-                    &statement
-                ])
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .output()?;
-            
-            if !output.stderr.is_empty() {
-                results.push(format!("SQL Error: {}", String::from_utf8_lossy(&output.stderr)));
+
+            let mut command = sandbox().prepare("sqlite3", parent_dir(temp_db.path()));
+            command.args(&[temp_db.path().to_str().unwrap(), &statement]);
+            let (_, stdout, stderr) = match output_within_limits(command, limits.run, None, start_time)? {
+                Ok(output) => output,
+                Err(timeout_response) => return Ok(timeout_response),
+            };
+
+            if !stderr.is_empty() {
+                results.push(format!("SQL Error: {}", String::from_utf8_lossy(&stderr)));
                 break;
             } else {
-                let stdout = String::from_utf8_lossy(&output.stdout);
+                let stdout = String::from_utf8_lossy(&stdout);
                 if statement.trim().to_uppercase().starts_with("SELECT") && !stdout.trim().is_empty() {
                     results.push(format!("Query: {}", statement));
                     results.push("Results:".to_string());
@@ -883,9 +1866,9 @@ impl CodeRunner for SqlRunner {
                 results.push("".to_string()); // Empty line for separation
             }
         }
-        
+
         let execution_time = start_time.elapsed().as_secs_f64();
-        
+
         Ok(CodeResponse {
             output: Some(results.join("\n")),
             error: None,
@@ -895,10 +1878,15 @@ impl CodeRunner for SqlRunner {
 }
 
 impl CodeRunner for TextRunner {
-    fn run(&self, code: &str) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+    fn run(
+        &self,
+        code: &str,
+        _stdin: Option<&str>,
+        _limits: &ExecutionLimits,
+    ) -> Result<CodeResponse, Box<dyn std::error::Error>> {
         let start_time = Instant::now();
         let execution_time = start_time.elapsed().as_secs_f64();
-        
+
         if code.trim().is_empty() {
             Ok(CodeResponse {
                 output: Some("(Empty text document)".to_string()),
@@ -915,6 +1903,422 @@ impl CodeRunner for TextRunner {
     }
 }
 
+// Warm language-server processes keyed by language name, so startup/indexing
+// cost is paid once per language, not once per /complete request. Each
+// language has its own inner Mutex so a hung completion round trip for one
+// language doesn't block /complete for the others; spawning a server (and its
+// initialize round trip) still happens under the outer lock.
+static LANGUAGE_SERVERS: OnceLock<Mutex<HashMap<String, Arc<Mutex<LanguageServer>>>>> = OnceLock::new();
+
+fn language_servers() -> &'static Mutex<HashMap<String, Arc<Mutex<LanguageServer>>>> {
+    LANGUAGE_SERVERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// The synthetic file URI and LSP languageId a snippet is announced under,
+// since the playground has no real project on disk to open.
+fn language_server_command(language: &str) -> Option<(&'static str, &'static str, &'static str)> {
+    match language {
+        "python" => Some(("pylsp", "python", "file:///playground/snippet.py")),
+        "rust" => Some(("rust-analyzer", "rust", "file:///playground/snippet.rs")),
+        "c" => Some(("clangd", "c", "file:///playground/snippet.c")),
+        "cpp" => Some(("clangd", "cpp", "file:///playground/snippet.cpp")),
+        _ => None,
+    }
+}
+
+// How long `request` waits for a matching response before giving up on a hung server.
+const LSP_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Reads one Content-Length-framed JSON-RPC message from stdout.
+fn read_lsp_message(stdout: &mut BufReader<std::process::ChildStdout>) -> std::io::Result<serde_json::Value> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if stdout.read_line(&mut line)? == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "language server closed its output",
+            ));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let content_length = content_length.ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, "missing Content-Length header")
+    })?;
+    let mut body = vec![0u8; content_length];
+    stdout.read_exact(&mut body)?;
+    serde_json::from_slice(&body).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+// A warm LSP server child process plus the JSON-RPC bookkeeping (request-id
+// counter, didChange version counter, whether didOpen has fired). stdout is
+// read on a background thread and forwarded over messages, so request can
+// recv_timeout instead of risking a read that never returns.
+struct LanguageServer {
+    child: Child,
+    stdin: std::process::ChildStdin,
+    messages: mpsc::Receiver<std::io::Result<serde_json::Value>>,
+    language_id: &'static str,
+    uri: &'static str,
+    next_id: u64,
+    version: u64,
+    opened: bool,
+}
+
+impl LanguageServer {
+    fn spawn(command: &str, language_id: &'static str, uri: &'static str) -> std::io::Result<Self> {
+        let mut child = Command::new(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("piped stdin");
+        let mut stdout = BufReader::new(child.stdout.take().expect("piped stdout"));
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || loop {
+            let message = read_lsp_message(&mut stdout);
+            let is_err = message.is_err();
+            if tx.send(message).is_err() || is_err {
+                break;
+            }
+        });
+
+        let mut server = LanguageServer {
+            child,
+            stdin,
+            messages: rx,
+            language_id,
+            uri,
+            next_id: 0,
+            version: 0,
+            opened: false,
+        };
+        server.request(
+            "initialize",
+            serde_json::json!({
+                "processId": std::process::id(),
+                "rootUri": null,
+                "capabilities": {},
+            }),
+        )?;
+        server.notify("initialized", serde_json::json!({}))?;
+        Ok(server)
+    }
+
+    // Announces or updates the one synthetic document, then asks for
+    // completions at line/character (zero-based, as LSP expects).
+    fn completion(&mut self, code: &str, line: u32, character: u32) -> std::io::Result<serde_json::Value> {
+        if self.opened {
+            self.version += 1;
+            self.notify(
+                "textDocument/didChange",
+                serde_json::json!({
+                    "textDocument": { "uri": self.uri, "version": self.version },
+                    "contentChanges": [{ "text": code }],
+                }),
+            )?;
+        } else {
+            self.opened = true;
+            self.notify(
+                "textDocument/didOpen",
+                serde_json::json!({
+                    "textDocument": {
+                        "uri": self.uri,
+                        "languageId": self.language_id,
+                        "version": self.version,
+                        "text": code,
+                    },
+                }),
+            )?;
+        }
+
+        self.request(
+            "textDocument/completion",
+            serde_json::json!({
+                "textDocument": { "uri": self.uri },
+                "position": { "line": line, "character": character },
+            }),
+        )
+    }
+
+    fn notify(&mut self, method: &str, params: serde_json::Value) -> std::io::Result<()> {
+        self.write_message(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        }))
+    }
+
+    // Sends a request and waits up to LSP_READ_TIMEOUT for the matching
+    // response id, discarding notifications pushed in the meantime.
+    fn request(&mut self, method: &str, params: serde_json::Value) -> std::io::Result<serde_json::Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.write_message(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        }))?;
+        let deadline = Instant::now() + LSP_READ_TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "language server did not respond in time",
+                ));
+            }
+            match self.messages.recv_timeout(remaining) {
+                Ok(Ok(message)) => {
+                    if message.get("id").and_then(|v| v.as_u64()) == Some(id) {
+                        return Ok(message.get("result").cloned().unwrap_or(serde_json::Value::Null));
+                    }
+                }
+                Ok(Err(e)) => return Err(e),
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "language server did not respond in time",
+                    ));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "language server closed its output",
+                    ));
+                }
+            }
+        }
+    }
+
+    fn write_message(&mut self, message: &serde_json::Value) -> std::io::Result<()> {
+        use std::io::Write;
+        let body = serde_json::to_vec(message)?;
+        write!(self.stdin, "Content-Length: {}\r\n\r\n", body.len())?;
+        self.stdin.write_all(&body)?;
+        self.stdin.flush()
+    }
+}
+
+impl Drop for LanguageServer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+// A single Monaco-shaped completion item; field names match the LSP wire format.
+#[derive(Deserialize, Serialize, Default)]
+struct CompletionItem {
+    label: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kind: Option<i64>,
+    #[serde(default, rename = "insertText", skip_serializing_if = "Option::is_none")]
+    insert_text: Option<String>,
+}
+
+// An LSP completion result is either a bare CompletionItem[] or a
+// CompletionList { isIncomplete, items }; pulls the items out of either shape.
+fn parse_completion_items(result: serde_json::Value) -> Vec<CompletionItem> {
+    let items = if result.is_array() {
+        result
+    } else {
+        result.get("items").cloned().unwrap_or(serde_json::Value::Array(Vec::new()))
+    };
+    serde_json::from_value(items).unwrap_or_default()
+}
+
+fn fetch_completions(
+    code: &str,
+    language: &str,
+    line: u32,
+    character: u32,
+) -> std::io::Result<Vec<CompletionItem>> {
+    let Some((command, language_id, uri)) = language_server_command(language) else {
+        return Ok(Vec::new());
+    };
+
+    // The map lookup/insert, and spawning + initializing a server the first
+    // time a language is seen, happen under the outer lock; the completion
+    // round trip itself runs against the per-language `Arc<Mutex<_>>` so it
+    // can't block `/complete` calls for other languages.
+    let server = {
+        let mut servers = language_servers().lock().unwrap();
+        if !servers.contains_key(language) {
+            let spawned = LanguageServer::spawn(command, language_id, uri)?;
+            servers.insert(language.to_string(), Arc::new(Mutex::new(spawned)));
+        }
+        servers.get(language).unwrap().clone()
+    };
+
+    match server.lock().unwrap().completion(code, line, character) {
+        Ok(result) => Ok(parse_completion_items(result)),
+        Err(e) => {
+            // The warm process died or desynced; drop it so the next
+            // request respawns a fresh one instead of repeating the error.
+            language_servers().lock().unwrap().remove(language);
+            Err(e)
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CompletionRequest {
+    code: String,
+    language: String,
+    line: u32,
+    character: u32,
+}
+
+#[derive(Serialize)]
+struct CompletionResponse {
+    items: Vec<CompletionItem>,
+}
+
+// Relays completion requests to the warm language server for req.language;
+// unsupported languages and server errors both degrade to an empty list.
+// fetch_completions is blocking, so it runs via web::block off the async worker.
+async fn complete(req: web::Json<CompletionRequest>) -> Result<HttpResponse> {
+    let CompletionRequest { code, language, line, character } = req.into_inner();
+    let items = web::block(move || fetch_completions(&code, &language, line, character))
+        .await
+        .ok()
+        .and_then(Result::ok)
+        .unwrap_or_default();
+    Ok(HttpResponse::Ok().json(CompletionResponse { items }))
+}
+
+// A shared code snippet, keyed by the short id returned from /share.
+#[derive(Clone, Serialize, Deserialize)]
+struct Snippet {
+    code: String,
+    language: String,
+}
+
+// Persists shared snippets; the trait exists so a SQL-backed store can be
+// dropped in later without touching the /share and /p/{id} handlers.
+trait SnippetStore: Send + Sync {
+    fn save(&self, id: &str, snippet: &Snippet) -> std::io::Result<()>;
+    fn load(&self, id: &str) -> std::io::Result<Option<Snippet>>;
+}
+
+// share_snippet only ever generates 10 lowercase hex chars; reject anything
+// else before it reaches the filesystem (a crafted `../`-style id could
+// otherwise walk path_for out of self.dir).
+fn is_valid_snippet_id(id: &str) -> bool {
+    id.len() == 10 && id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+// Stores each snippet as one JSON file under a directory, named by id.
+struct FileSnippetStore {
+    dir: std::path::PathBuf,
+}
+
+impl FileSnippetStore {
+    fn new(dir: impl AsRef<Path>) -> std::io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(FileSnippetStore { dir })
+    }
+
+    fn path_for(&self, id: &str) -> Option<std::path::PathBuf> {
+        if !is_valid_snippet_id(id) {
+            return None;
+        }
+        Some(self.dir.join(format!("{id}.json")))
+    }
+}
+
+impl SnippetStore for FileSnippetStore {
+    fn save(&self, id: &str, snippet: &Snippet) -> std::io::Result<()> {
+        let path = self.path_for(id).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid snippet id")
+        })?;
+        fs::write(path, serde_json::to_vec(snippet)?)
+    }
+
+    fn load(&self, id: &str) -> std::io::Result<Option<Snippet>> {
+        let path = match self.path_for(id) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        match fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes).ok()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+static SNIPPET_STORE: OnceLock<Box<dyn SnippetStore>> = OnceLock::new();
+
+fn snippet_store() -> &'static dyn SnippetStore {
+    SNIPPET_STORE
+        .get_or_init(|| {
+            Box::new(FileSnippetStore::new("shared_snippets").expect("create shared_snippets directory"))
+        })
+        .as_ref()
+}
+
+#[derive(Deserialize)]
+struct ShareRequest {
+    code: String,
+    language: String,
+}
+
+#[derive(Serialize)]
+struct ShareResponse {
+    id: String,
+    url: String,
+}
+
+// Persists the submitted code under a freshly generated short id and returns its /p/{id} permalink.
+async fn share_snippet(req: web::Json<ShareRequest>) -> Result<HttpResponse> {
+    let id = Uuid::new_v4().simple().to_string()[..10].to_string();
+    let snippet = Snippet {
+        code: req.code.clone(),
+        language: req.language.clone(),
+    };
+
+    match snippet_store().save(&id, &snippet) {
+        Ok(()) => Ok(HttpResponse::Ok().json(ShareResponse {
+            id: id.clone(),
+            url: format!("/p/{id}"),
+        })),
+        Err(e) => Ok(HttpResponse::InternalServerError().json(CodeResponse {
+            output: None,
+            error: Some(e.to_string()),
+            execution_time: 0.0,
+        })),
+    }
+}
+
+// Serves HTML_TEMPLATE with the snippet bootstrapped into window.__SNIPPET so Monaco loads it pre-filled.
+async fn view_snippet(id: web::Path<String>) -> Result<HttpResponse> {
+    match snippet_store().load(&id) {
+        Ok(Some(snippet)) => {
+            // Escape "</" so code containing "</script>" can't break out of
+            // the bootstrap script tag early.
+            let snippet_json = serde_json::to_string(&snippet)
+                .unwrap_or_else(|_| "{}".to_string())
+                .replace("</", "<\\/");
+            let bootstrap = format!("<script>window.__SNIPPET = {snippet_json};</script>");
+            let html = HTML_TEMPLATE.replace("<!--SNIPPET_BOOTSTRAP-->", &bootstrap);
+            Ok(HttpResponse::Ok().content_type("text/html").body(html))
+        }
+        Ok(None) => Ok(HttpResponse::NotFound().body("Snippet not found")),
+        Err(e) => Ok(HttpResponse::InternalServerError().body(e.to_string())),
+    }
+}
+
 // Route handlers
 async fn index() -> Result<HttpResponse> {
     Ok(HttpResponse::Ok()
@@ -922,44 +2326,83 @@ async fn index() -> Result<HttpResponse> {
         .body(HTML_TEMPLATE))
 }
 
-async fn run_code(req: web::Json<CodeRequest>) -> Result<HttpResponse> {
-    let code = &req.code;
-    let language = &req.language;
-    
+// Shared guardrail for /run and /run/ws: rejects empty submissions and the same naive Python import denylist.
+fn validate_request(code: &str, language: &str) -> Option<CodeResponse> {
     if code.trim().is_empty() {
-        return Ok(HttpResponse::BadRequest().json(CodeResponse {
+        return Some(CodeResponse {
             output: None,
             error: Some("No code provided".to_string()),
             execution_time: 0.0,
-        }));
+        });
     }
-    
+
     // Basic security check for Python
     if language == "python" {
         let dangerous_imports = ["os", "subprocess", "sys", "eval", "exec", "__import__"];
         if dangerous_imports.iter().any(|&dangerous| code.contains(dangerous)) {
-            return Ok(HttpResponse::BadRequest().json(CodeResponse {
+            return Some(CodeResponse {
                 output: None,
                 error: Some("Potentially dangerous code detected".to_string()),
                 execution_time: 0.0,
-            }));
+            });
         }
     }
-    
-    // Execute code based on language
-    let result = match language.as_str() {
-        "python" => PythonRunner.run(code),
-        "c" => CRunner.run(code),
-        "cpp" => CppRunner.run(code),
-        "java" => JavaRunner.run(code),
-        "kotlin" => KotlinRunner.run(code),
-        "javascript" => JavaScriptRunner.run(code),
-        "rust" => RustRunner.run(code),
-        "sql" => SqlRunner.run(code),
-        "text" => TextRunner.run(code),
-        _ => PythonRunner.run(code), // Default to Python
-    };
-    
+
+    None
+}
+
+fn dispatch_run(
+    code: &str,
+    language: &str,
+    stdin: Option<&str>,
+    limits: &ExecutionLimits,
+) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+    match language {
+        "python" => PythonRunner.run(code, stdin, limits),
+        "c" => CRunner.run(code, stdin, limits),
+        "cpp" => CppRunner.run(code, stdin, limits),
+        "java" => JavaRunner.run(code, stdin, limits),
+        "kotlin" => KotlinRunner.run(code, stdin, limits),
+        "javascript" => JavaScriptRunner.run(code, stdin, limits),
+        "rust" => RustRunner.run(code, stdin, limits),
+        "sql" => SqlRunner.run(code, stdin, limits),
+        "text" => TextRunner.run(code, stdin, limits),
+        _ => PythonRunner.run(code, stdin, limits), // Default to Python
+    }
+}
+
+fn dispatch_run_streaming(
+    code: &str,
+    language: &str,
+    stdin: Option<&str>,
+    limits: &ExecutionLimits,
+    sink: &mut dyn OutputSink,
+) -> Result<CodeResponse, Box<dyn std::error::Error>> {
+    match language {
+        "python" => PythonRunner.run_streaming(code, stdin, limits, sink),
+        "c" => CRunner.run_streaming(code, stdin, limits, sink),
+        "cpp" => CppRunner.run_streaming(code, stdin, limits, sink),
+        "java" => JavaRunner.run_streaming(code, stdin, limits, sink),
+        "kotlin" => KotlinRunner.run_streaming(code, stdin, limits, sink),
+        "javascript" => JavaScriptRunner.run_streaming(code, stdin, limits, sink),
+        "rust" => RustRunner.run_streaming(code, stdin, limits, sink),
+        "sql" => SqlRunner.run_streaming(code, stdin, limits, sink),
+        "text" => TextRunner.run_streaming(code, stdin, limits, sink),
+        _ => PythonRunner.run_streaming(code, stdin, limits, sink), // Default to Python
+    }
+}
+
+async fn run_code(req: web::Json<CodeRequest>) -> Result<HttpResponse> {
+    let code = &req.code;
+    let language = &req.language;
+
+    if let Some(rejection) = validate_request(code, language) {
+        return Ok(HttpResponse::BadRequest().json(rejection));
+    }
+
+    let limits = ExecutionLimits::default();
+    let result = dispatch_run(code, language, req.stdin.as_deref(), &limits);
+
     match result {
         Ok(response) => Ok(HttpResponse::Ok().json(response)),
         Err(e) => Ok(HttpResponse::InternalServerError().json(CodeResponse {
@@ -970,6 +2413,120 @@ async fn run_code(req: web::Json<CodeRequest>) -> Result<HttpResponse> {
     }
 }
 
+// One open /run/ws connection; runs CodeRunner::run_streaming on a background thread and relays StreamFrames back out.
+struct RunSocket;
+
+impl Actor for RunSocket {
+    type Context = ws::WebsocketContext<Self>;
+}
+
+// One incremental frame pushed from the background runner thread to RunSocket, then serialized onto the WebSocket.
+#[derive(Message, Serialize)]
+#[rtype(result = "()")]
+#[serde(tag = "event", rename_all = "lowercase")]
+enum StreamFrame {
+    Output { stream: &'static str, chunk: String },
+    Exit { code: i32, execution_time: f64 },
+}
+
+impl Handler<StreamFrame> for RunSocket {
+    type Result = ();
+
+    fn handle(&mut self, msg: StreamFrame, ctx: &mut Self::Context) {
+        if let Ok(json) = serde_json::to_string(&msg) {
+            ctx.text(json);
+        }
+    }
+}
+
+// Forwards each line to a RunSocket address as a StreamFrame::Output, bridging the background runner thread into the actor's mailbox.
+struct AddrSink {
+    addr: actix::Addr<RunSocket>,
+    // Whether any stderr line has been forwarded yet.
+    sent_stderr: bool,
+}
+
+impl OutputSink for AddrSink {
+    fn send_stdout(&mut self, line: String) {
+        self.addr.do_send(StreamFrame::Output { stream: "stdout", chunk: line });
+    }
+
+    fn send_stderr(&mut self, line: String) {
+        self.sent_stderr = true;
+        self.addr.do_send(StreamFrame::Output { stream: "stderr", chunk: line });
+    }
+}
+
+// Serializes a StreamFrame straight onto the socket, bypassing the actor mailbox, for frames raised synchronously.
+fn send_frame(ctx: &mut ws::WebsocketContext<RunSocket>, frame: StreamFrame) {
+    if let Ok(json) = serde_json::to_string(&frame) {
+        ctx.text(json);
+    }
+}
+
+impl actix::StreamHandler<Result<ws::Message, ws::ProtocolError>> for RunSocket {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        let msg = match msg {
+            Ok(msg) => msg,
+            Err(_) => return,
+        };
+
+        match msg {
+            ws::Message::Text(text) => {
+                let request: CodeRequest = match serde_json::from_str(&text) {
+                    Ok(request) => request,
+                    Err(e) => {
+                        send_frame(ctx, StreamFrame::Output { stream: "stderr", chunk: format!("Invalid request: {e}") });
+                        send_frame(ctx, StreamFrame::Exit { code: 1, execution_time: 0.0 });
+                        return;
+                    }
+                };
+
+                if let Some(rejection) = validate_request(&request.code, &request.language) {
+                    if let Some(error) = rejection.error {
+                        send_frame(ctx, StreamFrame::Output { stream: "stderr", chunk: error });
+                    }
+                    send_frame(ctx, StreamFrame::Exit { code: 1, execution_time: 0.0 });
+                    return;
+                }
+
+                let addr = ctx.address();
+                std::thread::spawn(move || {
+                    let start_time = Instant::now();
+                    let limits = ExecutionLimits::default();
+                    let mut sink = AddrSink { addr: addr.clone(), sent_stderr: false };
+                    let result = dispatch_run_streaming(
+                        &request.code,
+                        &request.language,
+                        request.stdin.as_deref(),
+                        &limits,
+                        &mut sink,
+                    );
+                    // Compile errors and "Time limit exceeded" come back only in the
+                    // response, never through the sink; forward them so the client sees them.
+                    if let Ok(CodeResponse { error: Some(error), .. }) = &result {
+                        if !sink.sent_stderr {
+                            sink.send_stderr(error.clone());
+                        }
+                    }
+                    let (code, execution_time) = match result {
+                        Ok(response) => (if response.error.is_some() { 1 } else { 0 }, response.execution_time),
+                        Err(_) => (1, start_time.elapsed().as_secs_f64()),
+                    };
+                    addr.do_send(StreamFrame::Exit { code, execution_time });
+                });
+            }
+            ws::Message::Ping(bytes) => ctx.pong(&bytes),
+            ws::Message::Close(reason) => ctx.close(reason),
+            _ => {}
+        }
+    }
+}
+
+async fn run_ws(req: HttpRequest, stream: web::Payload) -> Result<HttpResponse, actix_web::Error> {
+    ws::start(RunSocket, &req, stream)
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     env_logger::init();
@@ -984,12 +2541,35 @@ async fn main() -> std::io::Result<()> {
     println!("- node (for JavaScript)");
     println!("- cargo and rustc (for Rust)");
     println!("- sqlite3 (for SQL)");
-    
+    println!("For autocompletion (optional, /complete degrades to no suggestions without them):");
+    println!("- pylsp (for Python)");
+    println!("- rust-analyzer (for Rust)");
+    println!("- clangd (for C/C++)");
+    println!(
+        "Sandbox backend: {} (set SANDBOX=namespace to isolate runs via unshare/chroot; \
+         needs a rootfs at SANDBOX_ROOTFS, default /opt/sandbox-rootfs)",
+        match std::env::var("SANDBOX").as_deref() {
+            Ok("namespace") => "namespace",
+            _ => "none",
+        }
+    );
+    if std::env::var("SANDBOX").as_deref() != Ok("namespace") {
+        println!(
+            "Warning: without SANDBOX=namespace, each run's RLIMIT_NPROC cap is a budget \
+             shared by every concurrent request under this host's uid, not an isolated \
+             per-request fork-bomb guard."
+        );
+    }
+
     HttpServer::new(|| {
         App::new()
             .wrap(Logger::default())
             .route("/", web::get().to(index))
             .route("/run", web::post().to(run_code))
+            .route("/run/ws", web::get().to(run_ws))
+            .route("/complete", web::post().to(complete))
+            .route("/share", web::post().to(share_snippet))
+            .route("/p/{id}", web::get().to(view_snippet))
             .service(Files::new("/monaco-editor", "./monaco-editor").show_files_listing())
     })
     .bind("0.0.0.0:5003")?